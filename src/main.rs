@@ -12,14 +12,33 @@ xflags::xflags! {
         optional -q,--quiet
         /// Whether to display a chart instead of default, basic print output of the current state
         optional --chart
+        /// Whether to treat mouse button presses and releases as activity, in addition to movement.
+        optional --watch-buttons
+        /// Whether to treat scroll wheel movement as activity, in addition to movement.
+        optional --watch-scroll
+        /// An address (host:port) to serve ACTIVE/INACTIVE transitions on over TCP, so
+        /// other machines can react to this machine's mouse activity.
+        optional --serve addr: String
+        /// A `MILLISECONDS:PATH` pair defining an additional, deeper idle tier beyond
+        /// the baseline `--min-movement-gap`/`--on-inactive` one: once the mouse has
+        /// been idle for at least the given number of milliseconds, the executable at
+        /// `PATH` is run. May be passed more than once to define several escalating
+        /// tiers; they are sorted by their millisecond value regardless of order given.
+        repeated --tier tier: String
         /// The minimum gap between two readings to consider the mouse inactive, in milliseconds.
         /// Defaults to one second.
         optional --min-movement-gap milliseconds: u64
+        /// The accumulated movement, in pixels, that must be exceeded within a single
+        /// `--min-movement-gap` window before the mouse is considered actively moved.
+        /// Defaults to zero, meaning any report with a nonzero movement counts as activity.
+        optional --min-distance pixels: f64
         /// The name of a device to grap and thus block any other applications from seeing.
-        /// The passed name indicates which device to grab. If passed, any other mice will be 
+        /// The passed name indicates which device to grab. If passed, any other mice will be
         /// ignored by this program.
         /// On Linux, the name for a given device can be found using the `evdev` application.
-        /// Currently not supported on Windows.
+        /// On Windows, this only filters to the named device's Raw Input reports; it does
+        /// not stop other applications from also seeing that device's input, and only
+        /// movement is watched, so `--watch-buttons` and `--watch-scroll` have no effect.
         // Making the grabbing work on windows seems extremely complciated and fiddly.
         // No crate or simple working example that specifically eats the input was
         // from the grabbed device was found. Apparently what one neesd to do is set
@@ -27,8 +46,12 @@ xflags::xflags! {
         // call CallNextHookEx in the handler, to pass on or not pass on the input.
         // That's fine enough, but the closest thing to an example like that which I
         // was able to find acted strangely and incorrectly when run, in a way that
-        // makes me suspect undefined behaviour. Miri is no help here, because it 
+        // makes me suspect undefined behaviour. Miri is no help here, because it
         // doesn't support calling things like SetWindowsHookEx.
+        // Raw Input, on the other hand, is the officially sanctioned way to tell which
+        // physical mouse an event came from, without suppressing it, so `--grab-device`
+        // uses that on Windows instead: it filters to the named device rather than
+        // truly grabbing it.
         optional --grab-device device_name: String
         /// Output the version and exit
         optional --version
@@ -47,39 +70,65 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     match flags.grab_device.clone() {
         None => {
+            let watch_buttons = flags.watch_buttons;
+            let watch_scroll = flags.watch_scroll;
+
             let (sender, receiver) = std::sync::mpsc::channel();
 
             std::thread::spawn(move || {
                 activity_thread_main(receiver, flags)
             });
 
+            let mut last_pos: Option<(f64, f64)> = None;
+
             let listen_callback = move |event: rdev::Event| {
                 use rdev::EventType;
-        
+
                 match event.event_type {
-                    EventType::MouseMove {..} => {
+                    EventType::MouseMove { x, y } => {
+                        let (dx, dy) = match last_pos {
+                            Some((last_x, last_y)) => (x - last_x, y - last_y),
+                            None => (0.0, 0.0),
+                        };
+                        last_pos = Some((x, y));
+
+                        // If there's an error, we assume we won't be called again.
+                        if let Err(_) = sender.send(Report::Move { dx, dy }) {
+                            std::process::exit(1);
+                        }
+                    }
+                    EventType::ButtonPress(_) | EventType::ButtonRelease(_) if watch_buttons => {
                         // If there's an error, we assume we won't be called again.
-                        if let Err(_) = sender.send(()) {
+                        if let Err(_) = sender.send(Report::Click) {
+                            std::process::exit(1);
+                        }
+                    }
+                    EventType::Wheel { delta_x, delta_y } if watch_scroll => {
+                        // If there's an error, we assume we won't be called again.
+                        if let Err(_) = sender.send(Report::Scroll { dx: delta_x as f64, dy: delta_y as f64 }) {
                             std::process::exit(1);
                         }
                     }
                     _ => (),
                 }
             };
-        
+
             // This will call callback endlessly.
             rdev::listen(listen_callback).map_err(|e| format!("Error: {e:?}").into())
         },
         Some(target_device_name) => {
             #[cfg(target_family = "windows")]
             {
-                return Err(format!("Can't grab {target_device_name} because grabbing devices is not yet supported on Windows").into())
+                windows_watch_device(target_device_name, flags)
             }
 
             #[cfg(not(target_family = "windows"))]
             {
                 // TODO? Use evdev-rs instead of evdev since rdev uses evdev-rs?
 
+                let watch_buttons = flags.watch_buttons;
+                let watch_scroll = flags.watch_scroll;
+
                 // Capture the target mouse.
                 let devices = evdev::enumerate();
             
@@ -106,20 +155,50 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             
                     // Monitor the target mouse's events, sending signals to other thread in response.
                     loop {
-                        use evdev::{EventType, RelativeAxisCode};
-            
+                        use evdev::{EventType, RelativeAxisCode, KeyCode};
+
                         match device.fetch_events() {
                             Ok(iter) => {
                                 for event in iter {
-                                    if event.event_type() == EventType::RELATIVE 
-                                    && RelativeAxisCode(event.code()) == RelativeAxisCode::REL_Y { 
-                                        // If there's an error, we assume we won't be called again.
-                                        if let Err(_) = sender.send(()) {
-                                            std::process::exit(1);
+                                    match event.event_type() {
+                                        EventType::RELATIVE => {
+                                            let value = event.value() as f64;
+                                            let code = RelativeAxisCode(event.code());
+
+                                            let report = if code == RelativeAxisCode::REL_X {
+                                                Some(Report::Move { dx: value, dy: 0.0 })
+                                            } else if code == RelativeAxisCode::REL_Y {
+                                                Some(Report::Move { dx: 0.0, dy: value })
+                                            } else if watch_scroll
+                                            && (code == RelativeAxisCode::REL_WHEEL || code == RelativeAxisCode::REL_WHEEL_HI_RES) {
+                                                Some(Report::Scroll { dx: 0.0, dy: value })
+                                            } else {
+                                                None
+                                            };
+
+                                            if let Some(report) = report {
+                                                // If there's an error, we assume we won't be called again.
+                                                if let Err(_) = sender.send(report) {
+                                                    std::process::exit(1);
+                                                }
+                                            }
+                                        }
+                                        EventType::KEY if watch_buttons => {
+                                            let code = KeyCode(event.code());
+
+                                            if code == KeyCode::BTN_LEFT
+                                            || code == KeyCode::BTN_RIGHT
+                                            || code == KeyCode::BTN_MIDDLE {
+                                                // If there's an error, we assume we won't be called again.
+                                                if let Err(_) = sender.send(Report::Click) {
+                                                    std::process::exit(1);
+                                                }
+                                            }
                                         }
+                                        _ => {}
                                     }
                                 }
-                            }        
+                            }
                             Err(e) => { dbg!(e); }
                         }
                     }
@@ -131,19 +210,247 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 }
 
-fn activity_thread_main(receiver: std::sync::mpsc::Receiver<()>, flags: OnMouse) {
+// Filters to a single mouse's movement via the Raw Input API. This can tell devices
+// apart by `hDevice`, but unlike evdev's `grab`, it can't stop other applications
+// from also seeing the device's input; see the comment on `--grab-device` above.
+#[cfg(target_family = "windows")]
+fn windows_watch_device(target_device_name: String, flags: OnMouse) -> Result<(), Box<dyn std::error::Error>> {
+    use std::cell::RefCell;
+    use std::ptr;
+    use winapi::shared::minwindef::{LPARAM, LRESULT, UINT, WPARAM};
+    use winapi::shared::windef::HWND;
+    use winapi::um::libloaderapi::GetModuleHandleW;
+    use winapi::um::winuser::{
+        CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, GetRawInputData,
+        GetRawInputDeviceInfoW, RegisterClassW, RegisterRawInputDevices, TranslateMessage,
+        CW_USEDEFAULT, HWND_MESSAGE, MSG, RAWINPUT, RAWINPUTDEVICE, RAWINPUTHEADER,
+        RIDEV_INPUTSINK, RIDI_DEVICENAME, RID_INPUT, RIM_TYPEMOUSE, WM_INPUT, WNDCLASSW,
+    };
+
+    // The window procedure is a plain `extern "system" fn`, so it can't capture
+    // the `Sender`/target name; stash them here instead, one per thread, since
+    // we only ever create one such window on this thread.
+    thread_local! {
+        static STATE: RefCell<Option<(std::sync::mpsc::Sender<Report>, String)>> = RefCell::new(None);
+    }
+
+    unsafe extern "system" fn window_proc(
+        hwnd: HWND,
+        msg: UINT,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> LRESULT {
+        if msg == WM_INPUT {
+            let mut size: u32 = 0;
+
+            GetRawInputData(
+                lparam as _,
+                RID_INPUT,
+                ptr::null_mut(),
+                &mut size,
+                std::mem::size_of::<RAWINPUTHEADER>() as u32,
+            );
+
+            if size > 0 {
+                let mut buffer = vec![0u8; size as usize];
+
+                if GetRawInputData(
+                    lparam as _,
+                    RID_INPUT,
+                    buffer.as_mut_ptr() as _,
+                    &mut size,
+                    std::mem::size_of::<RAWINPUTHEADER>() as u32,
+                ) == size {
+                    // `buffer` is a `Vec<u8>`, only guaranteed 1-byte aligned, so a
+                    // `RAWINPUT` (which has 8-byte-aligned pointer-sized fields)
+                    // can't be referenced directly out of it; read it unaligned.
+                    let raw = ptr::read_unaligned(buffer.as_ptr() as *const RAWINPUT);
+
+                    if raw.header.dwType == RIM_TYPEMOUSE {
+                        STATE.with(|state| {
+                            if let Some((sender, target_device_name)) = &*state.borrow() {
+                                if device_name(raw.header.hDevice) == Some(target_device_name.clone()) {
+                                    let mouse = raw.data.mouse;
+                                    let dx = mouse.lLastX as f64;
+                                    let dy = mouse.lLastY as f64;
+
+                                    if dx != 0.0 || dy != 0.0 {
+                                        // If there's an error, we assume we won't be called again.
+                                        let _ = sender.send(Report::Move { dx, dy });
+                                    }
+                                }
+                            }
+                        });
+                    }
+                }
+            }
+        }
+
+        DefWindowProcW(hwnd, msg, wparam, lparam)
+    }
+
+    // Resolves the device name used to match `--grab-device`, the same way
+    // `evdev::Device::name` is used to match it on Linux.
+    unsafe fn device_name(device: winapi::shared::windef::HANDLE) -> Option<String> {
+        use std::ffi::OsString;
+        use std::os::windows::ffi::OsStringExt;
+
+        let mut size: u32 = 0;
+
+        GetRawInputDeviceInfoW(device as _, RIDI_DEVICENAME, ptr::null_mut(), &mut size);
+
+        if size == 0 {
+            return None;
+        }
+
+        let mut buffer = vec![0u16; size as usize];
+
+        let written = GetRawInputDeviceInfoW(
+            device as _,
+            RIDI_DEVICENAME,
+            buffer.as_mut_ptr() as _,
+            &mut size,
+        );
+
+        if written == u32::MAX {
+            return None;
+        }
+
+        let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+
+        Some(OsString::from_wide(&buffer[..len]).to_string_lossy().into_owned())
+    }
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+
+    std::thread::spawn(move || {
+        activity_thread_main(receiver, flags)
+    });
+
+    STATE.with(|state| {
+        *state.borrow_mut() = Some((sender, target_device_name));
+    });
+
+    unsafe {
+        let class_name: Vec<u16> = "on-mouse-raw-input".encode_utf16().chain(Some(0)).collect();
+
+        let instance = GetModuleHandleW(ptr::null());
+
+        let mut wc: WNDCLASSW = std::mem::zeroed();
+        wc.lpfnWndProc = Some(window_proc);
+        wc.hInstance = instance;
+        wc.lpszClassName = class_name.as_ptr();
+
+        RegisterClassW(&wc);
+
+        let hwnd = CreateWindowExW(
+            0,
+            class_name.as_ptr(),
+            ptr::null(),
+            0,
+            CW_USEDEFAULT, CW_USEDEFAULT, CW_USEDEFAULT, CW_USEDEFAULT,
+            HWND_MESSAGE,
+            ptr::null_mut(),
+            instance,
+            ptr::null_mut(),
+        );
+
+        if hwnd.is_null() {
+            return Err("Failed to create hidden message-only window for Raw Input".into());
+        }
+
+        let device = RAWINPUTDEVICE {
+            usUsagePage: 0x01, // Generic desktop controls
+            usUsage: 0x02, // Mouse
+            dwFlags: RIDEV_INPUTSINK,
+            hwndTarget: hwnd,
+        };
+
+        if RegisterRawInputDevices(&device, 1, std::mem::size_of::<RAWINPUTDEVICE>() as u32) == 0 {
+            return Err("Failed to register for raw mouse input".into());
+        }
+
+        println!("Watching for raw input from the matching device");
+
+        let mut msg: MSG = std::mem::zeroed();
+
+        while GetMessageW(&mut msg, ptr::null_mut(), 0, 0) > 0 {
+            TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+    }
+
+    Ok(())
+}
+
+fn activity_thread_main(receiver: std::sync::mpsc::Receiver<Report>, flags: OnMouse) {
     use std::process::Command;
 
+    let mut tiers: Vec<(Duration, PathBuf)> = flags.tier.iter().map(|tier| {
+        let (millis, path) = match tier.split_once(':') {
+            Some(pair) => pair,
+            None => {
+                eprintln!("Invalid --tier \"{tier}\": expected MILLISECONDS:PATH");
+                std::process::exit(1);
+            }
+        };
+
+        let millis: u64 = match millis.parse() {
+            Ok(millis) => millis,
+            Err(e) => {
+                eprintln!("Invalid --tier \"{tier}\": {e}");
+                std::process::exit(1);
+            }
+        };
+
+        (Duration::from_millis(millis), PathBuf::from(path))
+    }).collect();
+    tiers.sort_by_key(|(after, _)| *after);
+
+    let tier_durations: Vec<Duration> = tiers.iter().map(|(after, _)| *after).collect();
+    let tier_commands: Vec<PathBuf> = tiers.into_iter().map(|(_, command)| command).collect();
+
+    // When charting, every report's movement magnitude is plotted too, not just
+    // `Activity` transitions, so the main loop below needs its own handle on the
+    // chart thread's sender.
+    let mut chart_sample_sender: Option<std::sync::mpsc::Sender<ChartSample>> = None;
+
     let on_activity = {
         let on_active = flags.on_active;
         let on_inactive = flags.on_inactive;
         let quiet = flags.quiet;
         let chart = flags.chart;
+        let serve = flags.serve;
+
+        // `--serve` is orthogonal to the local display mode below: it's fine to
+        // combine e.g. `--chart --serve addr:port` or `-q --serve addr:port`, so
+        // it's set up independently rather than as another arm of `Mode`.
+        let serve_sender: Option<std::sync::mpsc::Sender<Activity>> = serve.map(|addr| {
+            // Bind on this thread, before spawning `serve_thread`, so a bad
+            // address (already in use, unparseable, etc.) fails fast with a
+            // clean error instead of the serve thread silently giving up and
+            // `on_activity` panicking later on a send into a dead channel.
+            let listener = match std::net::TcpListener::bind(&addr) {
+                Ok(listener) => listener,
+                Err(e) => {
+                    eprintln!("Failed to bind {addr}: {e}");
+                    std::process::exit(1);
+                }
+            };
+
+            let (serve_sender, serve_receiver) = std::sync::mpsc::channel();
+
+            std::thread::spawn(move || {
+                serve_thread(serve_receiver, listener)
+            });
+
+            serve_sender
+        });
 
         enum Mode {
             Quiet,
             Print,
-            Chart(std::sync::mpsc::Sender<Activity>),
+            Chart(std::sync::mpsc::Sender<ChartSample>),
         }
 
         let mode = match (quiet, chart) {
@@ -155,6 +462,8 @@ fn activity_thread_main(receiver: std::sync::mpsc::Receiver<()>, flags: OnMouse)
                     chart_thread(chart_receiver)
                 });
 
+                chart_sample_sender = Some(chart_sender.clone());
+
                 Mode::Chart(chart_sender)
             }
             (true, _) => Mode::Quiet,
@@ -167,9 +476,12 @@ fn activity_thread_main(receiver: std::sync::mpsc::Receiver<()>, flags: OnMouse)
                     use Activity::*;
 
                     match activity {
-                        Inactive => {
+                        Inactive { tier: 0 } => {
                             println!("INACTIVE");
                         },
+                        Inactive { tier } => {
+                            println!("INACTIVE (tier {tier})");
+                        },
                         Active => {
                             println!("ACTIVE");
                         },
@@ -177,10 +489,15 @@ fn activity_thread_main(receiver: std::sync::mpsc::Receiver<()>, flags: OnMouse)
                 },
                 Mode::Chart(ref chart_sender) => {
                     // If there's an error, we assume we won't be called again.
-                    chart_sender.send(activity).map_err(|e| format!("{e}"))?;
+                    chart_sender.send(ChartSample::Activity(activity)).map_err(|e| format!("{e}"))?;
                 }
             };
 
+            if let Some(ref serve_sender) = serve_sender {
+                // If there's an error, we assume we won't be called again.
+                serve_sender.send(activity).map_err(|e| format!("{e}"))?;
+            }
+
             match activity {
                 Activity::Active => {
                     if let Some(ref on_active) = on_active {
@@ -192,7 +509,7 @@ fn activity_thread_main(receiver: std::sync::mpsc::Receiver<()>, flags: OnMouse)
                         }
                     }
                 }
-                Activity::Inactive => {
+                Activity::Inactive { tier: 0 } => {
                     if let Some(ref on_inactive) = on_inactive {
                         if let Err(e) = Command::new::<&PathBuf>(&on_inactive)
                             .stdout(std::process::Stdio::null())
@@ -202,6 +519,16 @@ fn activity_thread_main(receiver: std::sync::mpsc::Receiver<()>, flags: OnMouse)
                         }
                     }
                 }
+                Activity::Inactive { tier } => {
+                    if let Some(command) = tier_commands.get(tier - 1) {
+                        if let Err(e) = Command::new::<&PathBuf>(command)
+                            .stdout(std::process::Stdio::null())
+                            .stderr(std::process::Stdio::null())
+                            .spawn() {
+                            return Err(format!("Failed to run {}: {e}", command.display()));
+                        }
+                    }
+                }
             }
 
             Ok(())
@@ -211,17 +538,35 @@ fn activity_thread_main(receiver: std::sync::mpsc::Receiver<()>, flags: OnMouse)
     let get_now = Box::new(Instant::now);
 
     let min_movement_gap: Duration = flags.min_movement_gap.map(Duration::from_millis).unwrap_or(Duration::from_secs(1));
+    let min_distance: f64 = flags.min_distance.unwrap_or(0.0);
 
     let mut handler: Handler =
-        get_handler(on_activity, get_now, min_movement_gap);
+        get_handler(on_activity, get_now, min_movement_gap, min_distance, tier_durations);
 
     let timeout: Duration = min_movement_gap.div_f32(4.0);
 
 
     loop {
         match receiver.recv_timeout(timeout) {
-            Ok(_) => {
-                if let Err(e) = handler(Event::Mousemove) {
+            Ok(report) => {
+                if let Some(ref chart_sample_sender) = chart_sample_sender {
+                    let magnitude = match report {
+                        Report::Move { dx, dy } => (dx * dx + dy * dy).sqrt(),
+                        Report::Scroll { dx, dy } => (dx * dx + dy * dy).sqrt(),
+                        Report::Click => 1.0,
+                    };
+
+                    // If there's an error, we assume we won't be called again.
+                    let _ = chart_sample_sender.send(ChartSample::Magnitude(magnitude));
+                }
+
+                let event = match report {
+                    Report::Move { dx, dy } => Event::Mousemove { dx, dy },
+                    Report::Click => Event::Click,
+                    Report::Scroll { dx, dy } => Event::Scroll { dx, dy },
+                };
+
+                if let Err(e) = handler(event) {
                     drop(receiver);
                     panic!("{e}");
                 }
@@ -237,60 +582,105 @@ fn activity_thread_main(receiver: std::sync::mpsc::Receiver<()>, flags: OnMouse)
     }
 }
 
+// A single report of mouse activity, as read off the input thread, before it has
+// been classified into an `Event` for the `Handler`.
+#[derive(Copy, Clone)]
+enum Report {
+    Move { dx: f64, dy: f64 },
+    Click,
+    Scroll { dx: f64, dy: f64 },
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 enum Activity {
-    Inactive,
+    // `tier` 0 is the baseline `--min-movement-gap`/`--on-inactive` idle state;
+    // higher tiers correspond to `--tier` entries, in ascending order by duration.
+    Inactive { tier: usize },
     Active,
 }
 
-fn chart_thread(receiver: std::sync::mpsc::Receiver<Activity>) {
+// A value sent to the chart thread: either a per-report movement magnitude to
+// plot, or an `Activity` transition to shade the classification band with.
+enum ChartSample {
+    Magnitude(f64),
+    Activity(Activity),
+}
+
+fn chart_thread(receiver: std::sync::mpsc::Receiver<ChartSample>) {
     use ratatui::crossterm::event::{self, Event, KeyCode, KeyModifiers};
 
     const COUNT: usize = 200;
 
     let mut window: Vec<(f64, f64)> = Vec::with_capacity(COUNT);
+    let mut band: Vec<(f64, f64)> = Vec::with_capacity(COUNT);
 
-    let mut update_and_render = move |frame: &mut ratatui::Frame, actvity| {
-        use ratatui::prelude::*;
-        use ratatui::style::{Modifier, Style};
-        use ratatui::text::Span;
-        use ratatui::widgets::{Axis, Block, Chart, Dataset};
-
+    let push_sample = |window: &mut Vec<(f64, f64)>, band: &mut Vec<(f64, f64)>, magnitude: f64, active: bool| {
         if window.len() >= COUNT {
             window.remove(0);
+            band.remove(0);
 
             for (i, el) in window.iter_mut().enumerate() {
                 el.0 = i as f64;
             }
-        }
-        window.push((
-            window.len() as f64,
-            match actvity {
-                Activity::Inactive => -1.,
-                Activity::Active => 1.,
+            for (i, el) in band.iter_mut().enumerate() {
+                el.0 = i as f64;
             }
-        ));
+        }
+
+        let x = window.len() as f64;
+
+        window.push((x, magnitude));
+        band.push((x, if active { 1.0 } else { 0.0 }));
+    };
+
+    let render = |frame: &mut ratatui::Frame, window: &[(f64, f64)], band: &[(f64, f64)]| {
+        use ratatui::prelude::*;
+        use ratatui::style::{Modifier, Style};
+        use ratatui::text::Span;
+        use ratatui::widgets::{Axis, Block, Chart, Dataset, GraphType};
 
         let x_min = 0.0;
-        let x_max = window.len() as f64;
+        let x_max = COUNT as f64;
+
+        let y_max = window.iter()
+            .map(|(_, magnitude)| *magnitude)
+            .fold(0.0_f64, f64::max)
+            .max(1.0);
 
         let x_labels = vec![
             Span::styled(
                 format!("{}", x_min),
                 Style::default().add_modifier(Modifier::BOLD),
             ),
-            Span::raw(format!("{}", window.len() / 2)),
+            Span::raw(format!("{}", COUNT / 2)),
             Span::styled(
                 format!("{}", x_max),
                 Style::default().add_modifier(Modifier::BOLD),
             ),
         ];
+        let y_labels = vec![
+            Span::styled("0", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(format!("{:.0}", y_max / 2.0)),
+            Span::styled(format!("{:.0}", y_max), Style::default().add_modifier(Modifier::BOLD)),
+        ];
+
+        // Rescale the 0/1 active/inactive band onto the movement magnitude's
+        // y range, so it reads as a shaded strip behind the line.
+        let scaled_band: Vec<(f64, f64)> = band.iter().map(|(x, active)| (*x, *active * y_max)).collect();
+
         let datasets = vec![
             Dataset::default()
-                .name("Activity")
+                .name("Active")
                 .marker(symbols::Marker::Block)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(Color::DarkGray))
+                .data(&scaled_band),
+            Dataset::default()
+                .name("Movement")
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
                 .style(Style::default().fg(Color::Cyan))
-                .data(&window),
+                .data(window),
         ];
 
         let chart = Chart::new(datasets)
@@ -304,10 +694,10 @@ fn chart_thread(receiver: std::sync::mpsc::Receiver<Activity>) {
             )
             .y_axis(
                 Axis::default()
-                    .title("Activity")
+                    .title("Movement")
                     .style(Style::default().fg(Color::Gray))
-                    .labels(["-1".bold(), "0".into(), "1".bold()])
-                    .bounds([-1.0, 1.0]),
+                    .labels(y_labels)
+                    .bounds([0.0, y_max]),
             );
 
         frame.render_widget(chart, frame.area());
@@ -318,18 +708,31 @@ fn chart_thread(receiver: std::sync::mpsc::Receiver<Activity>) {
     let per_frame = Duration::from_millis(80);
     let half_frame = per_frame.div_f32(2.);
 
-    let mut last_activity = Activity::Inactive;
+    let mut last_activity = Activity::Inactive { tier: 0 };
+    let mut paused = false;
 
     loop {
-        match receiver.recv_timeout(half_frame) {
-            Ok(activity) => {
-                last_activity = activity;
+        // Drain whatever samples have piled up since the last frame; movement
+        // reports can arrive far more often than the chart redraws.
+        loop {
+            match receiver.try_recv() {
+                Ok(ChartSample::Magnitude(magnitude)) => {
+                    if !paused {
+                        push_sample(&mut window, &mut band, magnitude, last_activity == Activity::Active);
+                    }
+                }
+                Ok(ChartSample::Activity(activity)) => {
+                    last_activity = activity;
+                }
+                Err(std::sync::mpsc::TryRecvError::Empty) => break,
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    ratatui::restore();
+                    std::process::exit(0);
+                }
             }
-            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
-            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
         }
 
-        terminal.draw(|frame| update_and_render(frame, last_activity))
+        terminal.draw(|frame| render(frame, &window, &band))
             .expect("terminal drawing should work");
 
         if event::poll(half_frame).expect("terminal events should work") {
@@ -338,6 +741,7 @@ fn chart_thread(receiver: std::sync::mpsc::Receiver<Activity>) {
                     match key_event.code {
                         KeyCode::Char('q') => break,
                         KeyCode::Char('c' | 'C') if key_event.modifiers == KeyModifiers::CONTROL => break,
+                        KeyCode::Char('p') => paused = !paused,
                         _ => {}
                     }
                 }
@@ -351,8 +755,68 @@ fn chart_thread(receiver: std::sync::mpsc::Receiver<Activity>) {
     std::process::exit(0);
 }
 
+// A magic byte plus a one-byte protocol version, written to every newly
+// connected client so that a client speaking a different version of this
+// protocol can reject the connection instead of misinterpreting the stream.
+const SERVE_MAGIC_BYTE: u8 = 0x6F; // 'o', for on-mouse
+const SERVE_PROTOCOL_VERSION: u8 = 1;
+
+const SERVE_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
+
+fn serve_thread(receiver: std::sync::mpsc::Receiver<Activity>, listener: std::net::TcpListener) {
+    use std::io::Write;
+    use std::net::TcpStream;
+    use std::sync::{Arc, Mutex};
+
+    let clients: Arc<Mutex<Vec<TcpStream>>> = Arc::new(Mutex::new(Vec::new()));
+
+    {
+        let clients = clients.clone();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = match stream {
+                    Ok(stream) => stream,
+                    Err(_) => continue,
+                };
+
+                if stream.write_all(&[SERVE_MAGIC_BYTE, SERVE_PROTOCOL_VERSION]).is_err() {
+                    continue;
+                }
+
+                clients.lock().unwrap().push(stream);
+            }
+        });
+    }
+
+    fn send_frame(stream: &mut TcpStream, payload: &[u8]) -> std::io::Result<()> {
+        stream.write_all(&(payload.len() as u32).to_be_bytes())?;
+        stream.write_all(payload)?;
+
+        Ok(())
+    }
+
+    loop {
+        // An empty frame acts as a heartbeat, so dead peers can be detected
+        // and dropped even when the mouse stays in one state for a while.
+        let payload: &[u8] = match receiver.recv_timeout(SERVE_HEARTBEAT_INTERVAL) {
+            Ok(Activity::Active) => b"ACTIVE",
+            Ok(Activity::Inactive { .. }) => b"INACTIVE",
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => b"",
+            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
+        };
+
+        let mut clients = clients.lock().unwrap();
+
+        // Drop any client a write failed on; that's how we notice disconnects.
+        clients.retain_mut(|stream| send_frame(stream, payload).is_ok());
+    }
+}
+
 enum Event {
-    Mousemove,
+    Mousemove { dx: f64, dy: f64 },
+    Click,
+    Scroll { dx: f64, dy: f64 },
     TimePassed,
 }
 
@@ -364,29 +828,74 @@ fn get_handler(
     mut on_activity: OnActivity,
     mut get_now: GetNow,
     min_movement_gap: Duration,
+    min_distance: f64,
+    tiers: Vec<Duration>,
 ) -> Handler {
     let mut last_move_time = get_now();
     let mut last_is_active = false;
+    let mut accumulated_distance: f64 = 0.0;
+    let mut current_tier: usize = 0;
+
+    // Tier 0 is the baseline `--min-movement-gap` threshold; any `--tier`
+    // entries (already sorted ascending) extend the ladder beyond that.
+    let mut thresholds = Vec::with_capacity(tiers.len() + 1);
+    thresholds.push(min_movement_gap);
+    thresholds.extend(tiers);
 
     Box::new(move |event: Event| -> Result<(), String> {
-        let mut is_active = false;
+        let is_active;
 
         match event {
-            Event::Mousemove => {
-                is_active = true;
+            Event::Mousemove { dx, dy } => {
+                let now = get_now();
+                // A report arriving a whole `--min-movement-gap` after the previous
+                // one starts a new window, so distance doesn't carry across windows.
+                if now.duration_since(last_move_time) >= thresholds[0] {
+                    accumulated_distance = 0.0;
+                }
+                accumulated_distance += (dx * dx + dy * dy).sqrt();
+                last_move_time = now;
+                // Any movement, even sub-`--min-distance` jitter, bumps
+                // `last_move_time` and so restarts the idle clock; reset the
+                // tier ladder along with it so a later genuine idle period can
+                // still climb back up through tiers already fired before this
+                // jitter, instead of being stuck requiring a tier deeper than
+                // whatever was reached previously.
+                current_tier = 0;
+
+                is_active = last_is_active || accumulated_distance > min_distance;
+            },
+            Event::Click | Event::Scroll { .. } => {
                 last_move_time = get_now();
+                is_active = true;
             },
             Event::TimePassed => {
-                if last_is_active {
-                    let now = get_now();
-                    let since = now.duration_since(last_move_time);
+                let now = get_now();
+                let since = now.duration_since(last_move_time);
 
-                    if since < min_movement_gap {
+                if last_is_active {
+                    if since < thresholds[0] {
                         // Okay, check again later.
                         return Ok(())
                     } else {
                         is_active = false;
+                        accumulated_distance = 0.0;
                     }
+                } else {
+                    // Already idle; see if a deeper tier has been crossed.
+                    let highest_crossed = thresholds.iter()
+                        .enumerate()
+                        .filter(|(_, threshold)| since >= **threshold)
+                        .map(|(tier, _)| tier)
+                        .max();
+
+                    return match highest_crossed {
+                        Some(tier) if tier > current_tier => {
+                            current_tier = tier;
+                            on_activity(Activity::Inactive { tier })
+                        }
+                        _ => Ok(()),
+                    };
                 }
             },
         }
@@ -394,10 +903,12 @@ fn get_handler(
         match (last_is_active, is_active) {
             (true, true) | (false, false) => {},
             (false, true) => {
+                current_tier = 0;
                 on_activity(Activity::Active)?;
             },
             (true, false) => {
-                on_activity(Activity::Inactive)?;
+                current_tier = 0;
+                on_activity(Activity::Inactive { tier: 0 })?;
             },
         }
 
@@ -434,9 +945,9 @@ fn this_sequence_produces_the_expected_calls() {
         Ok(())
     });
 
-    let mut handler = get_handler(on_activity, get_now, min_movement_gap);
+    let mut handler = get_handler(on_activity, get_now, min_movement_gap, 0.0, vec![]);
 
-    handler(Event::Mousemove).unwrap();
+    handler(Event::Mousemove { dx: 1.0, dy: 0.0 }).unwrap();
 
     handler(Event::TimePassed).unwrap();
     handler(Event::TimePassed).unwrap();
@@ -445,7 +956,7 @@ fn this_sequence_produces_the_expected_calls() {
     assert_eq!(&*(calls.read().unwrap()), &vec![Activity::Active]);
 
     for _ in 0..5 {
-        handler(Event::Mousemove).unwrap();
+        handler(Event::Mousemove { dx: 1.0, dy: 0.0 }).unwrap();
 
         handler(Event::TimePassed).unwrap();
         handler(Event::TimePassed).unwrap();
@@ -459,5 +970,142 @@ fn this_sequence_produces_the_expected_calls() {
         handler(Event::TimePassed).unwrap();
     }
 
-    assert_eq!(&*(calls.read().unwrap()), &vec![Activity::Active, Activity::Inactive]);
+    assert_eq!(&*(calls.read().unwrap()), &vec![Activity::Active, Activity::Inactive { tier: 0 }]);
+}
+
+#[test]
+fn a_sub_min_distance_nudge_does_not_permanently_stick_the_tier_ladder() {
+    let min_movement_gap = Duration::from_nanos(4);
+    let tier_duration = Duration::from_nanos(8);
+    let timeout = Duration::from_nanos(1);
+    let min_distance = 10.0;
+
+    let mut base_instant = Instant::now();
+
+    let get_now = Box::new(move || {
+        base_instant = base_instant.checked_add(timeout).unwrap();
+        base_instant
+    });
+
+    use std::sync::Arc;
+    use std::sync::RwLock;
+
+    let calls = Arc::new(RwLock::new(vec![]));
+
+    let active_handle: Arc<_> = calls.clone();
+    let on_activity = Box::new(move |activity| {
+        active_handle.write().unwrap().push(activity);
+        Ok(())
+    });
+
+    let mut handler = get_handler(on_activity, get_now, min_movement_gap, min_distance, vec![tier_duration]);
+
+    // A genuine, over-threshold movement, then idle long enough to climb to tier 1.
+    handler(Event::Mousemove { dx: 20.0, dy: 0.0 }).unwrap();
+
+    for _ in 0..20 {
+        handler(Event::TimePassed).unwrap();
+    }
+
+    assert_eq!(
+        &*(calls.read().unwrap()),
+        &vec![Activity::Active, Activity::Inactive { tier: 0 }, Activity::Inactive { tier: 1 }],
+    );
+
+    // A desk bump: movement too small to cross `--min-distance`, so it must not
+    // reactivate, but it does restart the idle clock and so should reset the
+    // tier ladder rather than leaving it pinned at its previous high-water mark.
+    handler(Event::Mousemove { dx: 1.0, dy: 0.0 }).unwrap();
+
+    // A fresh idle session long enough to reach tier 1 again should still fire
+    // the ladder, not be stuck requiring a tier deeper than the one already hit.
+    for _ in 0..20 {
+        handler(Event::TimePassed).unwrap();
+    }
+
+    assert_eq!(
+        &*(calls.read().unwrap()),
+        &vec![
+            Activity::Active,
+            Activity::Inactive { tier: 0 },
+            Activity::Inactive { tier: 1 },
+            Activity::Inactive { tier: 1 },
+        ],
+    );
+}
+
+#[test]
+fn a_scroll_counts_as_activity_immediately_regardless_of_min_distance() {
+    // Scroll (like a click) is activity in its own right, not a proxy for
+    // movement, so it must not be swallowed by `--min-distance` the way
+    // sub-threshold movement jitter is.
+    let min_movement_gap = Duration::from_nanos(4);
+    let timeout = Duration::from_nanos(1);
+    let min_distance = 10.0;
+
+    let mut base_instant = Instant::now();
+
+    let get_now = Box::new(move || {
+        base_instant = base_instant.checked_add(timeout).unwrap();
+        base_instant
+    });
+
+    use std::sync::Arc;
+    use std::sync::RwLock;
+
+    let calls = Arc::new(RwLock::new(vec![]));
+
+    let active_handle: Arc<_> = calls.clone();
+    let on_activity = Box::new(move |activity| {
+        active_handle.write().unwrap().push(activity);
+        Ok(())
+    });
+
+    let mut handler = get_handler(on_activity, get_now, min_movement_gap, min_distance, vec![]);
+
+    // A single small scroll notch, well below `--min-distance`, still activates.
+    handler(Event::Scroll { dx: 0.0, dy: 1.0 }).unwrap();
+
+    assert_eq!(&*(calls.read().unwrap()), &vec![Activity::Active]);
+}
+
+#[test]
+fn sub_min_distance_movement_does_not_activate_but_accumulates() {
+    let min_movement_gap = Duration::from_nanos(4);
+    let timeout = Duration::from_nanos(1);
+    let min_distance = 10.0;
+
+    let mut base_instant = Instant::now();
+
+    let get_now = Box::new(move || {
+        base_instant = base_instant.checked_add(timeout).unwrap();
+        base_instant
+    });
+
+    use std::sync::Arc;
+    use std::sync::RwLock;
+
+    let calls = Arc::new(RwLock::new(vec![]));
+
+    let active_handle: Arc<_> = calls.clone();
+    let on_activity = Box::new(move |activity| {
+        active_handle.write().unwrap().push(activity);
+        Ok(())
+    });
+
+    let mut handler = get_handler(on_activity, get_now, min_movement_gap, min_distance, vec![]);
+
+    // Individually sub-threshold moves, below the `--min-distance`, must not
+    // mark the mouse active.
+    for _ in 0..3 {
+        handler(Event::Mousemove { dx: 3.0, dy: 0.0 }).unwrap();
+    }
+
+    assert_eq!(&*(calls.read().unwrap()), &Vec::<Activity>::new());
+
+    // But the accumulated distance across those reports eventually crosses
+    // the threshold, the same as one larger move would.
+    handler(Event::Mousemove { dx: 3.0, dy: 0.0 }).unwrap();
+
+    assert_eq!(&*(calls.read().unwrap()), &vec![Activity::Active]);
 }
\ No newline at end of file